@@ -2,21 +2,106 @@ use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, ProtocolError>;
 
-#[derive(Debug, Error, Clone)]
-pub struct ProtocolError(pub &'static str);
+/// Structured protocol error, replacing the former `ProtocolError(&'static
+/// str)` newtype (and the `Box::leak` hack it needed to build a message
+/// dynamically). Each variant carries owned context, and the ones wrapping
+/// another error keep it as `#[source]` instead of discarding it. Splitting
+/// errors into categories lets callers -- notably the retry middleware --
+/// tell a retryable transport/GraphQL failure from a fatal crypto or
+/// validation error instead of string-matching `.0`.
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    /// The Nash GraphQL server itself reported an error for the request
+    /// (its `errors` array was non-empty), as opposed to returning a
+    /// well-formed response that just didn't say what was expected -- see
+    /// [`UnexpectedResponse`](Self::UnexpectedResponse) for that case. A
+    /// server-side error is often transient (overload, a dropped
+    /// connection upstream of the server), so this is retried the same as
+    /// [`Transport`](Self::Transport).
+    ///
+    /// Default for any server-reported error, since nothing here parses the
+    /// `errors` array into a machine-checkable code that would split
+    /// transient failures from permanent ones (bad params, insufficient
+    /// balance, a failed auth check). A caller that *can* tell the two apart
+    /// should report a permanent one as
+    /// [`GraphqlPermanent`](Self::GraphqlPermanent) instead, which is never
+    /// retried.
+    #[error("graphql error: {0}")]
+    Graphql(String),
+
+    /// Same as [`Graphql`](Self::Graphql), but for a server-reported error
+    /// already known to be a permanent rejection of the request rather than
+    /// a transient one -- retrying would just fail identically every time.
+    /// No call site in this crate constructs this yet; see the note on
+    /// [`Graphql`](Self::Graphql) for why.
+    #[error("graphql error: {0}")]
+    GraphqlPermanent(String),
+
+    /// The request round-tripped fine and the server raised no error, but
+    /// the response's content didn't match what this request expected (a
+    /// confirmation of the wrong state, a missing field). Retrying sends
+    /// the identical request again, which will parse into the identical
+    /// response -- so unlike [`Graphql`](Self::Graphql), this is never
+    /// retryable.
+    #[error("unexpected response: {0}")]
+    UnexpectedResponse(String),
+
+    /// A request or response failed to serialize/deserialize.
+    #[error("serialization error: {message}")]
+    Serialization {
+        message: String,
+        #[source]
+        source: Option<serde_json::Error>,
+    },
+
+    /// A cryptographic operation (signing, DH secret generation, hashing)
+    /// failed.
+    #[error("crypto error: {0}")]
+    Crypto(String),
+
+    /// An R-value (or other) pool ran out before it could be refilled.
+    #[error("pool exhausted: {0}")]
+    PoolExhausted(String),
+
+    /// A `Prefix` byte didn't match any known operation.
+    #[error("invalid prefix: {0}")]
+    InvalidPrefix(String),
+
+    /// An `Address`/`PublicKey` was used somewhere that required a
+    /// different chain or didn't match what was expected.
+    #[error("address mismatch: {0}")]
+    AddressMismatch(String),
+
+    /// The network round trip to execute a request failed, or a dependency
+    /// needed to perform it (an RPC client, a hardware wallet transport)
+    /// isn't wired up yet.
+    #[error("transport error: {0}")]
+    Transport(String),
+
+    /// Anything that doesn't fit the categories above, e.g. input
+    /// validation failures. New call sites should prefer a specific variant
+    /// when one applies.
+    #[error("{0}")]
+    Other(String),
+}
 
 impl ProtocolError {
-    // FIXME: this is a terrible hack. Added temporarily because so much code was already relying
-    // upon &'static str creation of protocol errors, but migrate everything to String and allow
-    // construction of error messages dynamically
-    pub fn coerce_static_from_str(error_str: &str) -> Self {
-        let coerce_static = Box::leak(error_str.to_string().into_boxed_str());
-        ProtocolError(coerce_static)
+    /// Whether the failure that produced this error might succeed on retry,
+    /// as opposed to a fatal crypto, validation, or serialization error that
+    /// would just fail the same way again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ProtocolError::Transport(_) | ProtocolError::Graphql(_))
     }
 }
 
-impl std::fmt::Display for ProtocolError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+impl From<&'static str> for ProtocolError {
+    fn from(message: &'static str) -> Self {
+        ProtocolError::Other(message.to_string())
     }
-}
\ No newline at end of file
+}
+
+impl From<String> for ProtocolError {
+    fn from(message: String) -> Self {
+        ProtocolError::Other(message)
+    }
+}
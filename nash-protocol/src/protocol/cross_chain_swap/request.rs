@@ -0,0 +1,124 @@
+use super::types::{HtlcLockRequest, HtlcRedeemRequest, HtlcRefundRequest};
+use crate::errors::Result;
+use crate::graphql;
+use crate::graphql::{htlc_lock, htlc_redeem, htlc_refund};
+use crate::protocol::State;
+use crate::types::blockchain::Prefix;
+
+use futures::lock::Mutex;
+use graphql_client::GraphQLQuery;
+use std::sync::Arc;
+
+impl HtlcLockRequest {
+    /// Bytes signed for this request, in the binary form
+    /// `Signer::sign_payload` expects: the prefix, the locker and
+    /// counterparty addresses decoded to their raw bytes (rather than the
+    /// ASCII characters of the hex strings used on the wire), the hash, and
+    /// the big-endian timeout. The `blockchain`, `asset` and `amount` fields
+    /// that `make_query` also sends are not part of the signed bytes.
+    fn payload_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Prefix::HtlcLock.to_bytes().to_vec();
+        bytes.extend_from_slice(&self.lock.locker.to_bytes()?);
+        bytes.extend_from_slice(&self.lock.counterparty.to_bytes()?);
+        bytes.extend_from_slice(&self.lock.hash.0);
+        bytes.extend_from_slice(&self.lock.timeout.to_be_bytes());
+        Ok(bytes)
+    }
+
+    pub async fn make_query(
+        &self,
+        state: Arc<Mutex<State>>,
+    ) -> Result<graphql_client::QueryBody<htlc_lock::Variables>> {
+        let signature = {
+            let mut state = state.lock().await;
+            hex::encode(
+                state
+                    .signer()?
+                    .sign_payload(self.lock.chain, &self.payload_bytes()?)
+                    .await?,
+            )
+        };
+        let payload = htlc_lock::HtlcLockParams {
+            prefix: Prefix::HtlcLock.to_bytes().to_vec(),
+            blockchain: format!("{:?}", self.lock.chain),
+            asset: format!("{:?}", self.lock.asset),
+            amount: self.lock.amount.clone(),
+            locker: self.lock.locker.to_hex_str(),
+            counterparty: self.lock.counterparty.to_hex_str(),
+            hash: hex::encode(&self.lock.hash.0),
+            timeout: self.lock.timeout,
+            signature,
+        };
+        Ok(graphql::HtlcLock::build_query(htlc_lock::Variables { payload }))
+    }
+}
+
+impl HtlcRedeemRequest {
+    /// Bytes signed for this request, in the binary form
+    /// `Signer::sign_payload` expects: the prefix, the hash, and the
+    /// preimage. The `blockchain` field that `make_query` also sends is not
+    /// part of the signed bytes.
+    fn payload_bytes(&self) -> Vec<u8> {
+        let mut bytes = Prefix::HtlcRedeem.to_bytes().to_vec();
+        bytes.extend_from_slice(&self.lock.hash.0);
+        bytes.extend_from_slice(&self.preimage.0);
+        bytes
+    }
+
+    pub async fn make_query(
+        &self,
+        state: Arc<Mutex<State>>,
+    ) -> Result<graphql_client::QueryBody<htlc_redeem::Variables>> {
+        let signature = {
+            let mut state = state.lock().await;
+            hex::encode(
+                state
+                    .signer()?
+                    .sign_payload(self.lock.chain, &self.payload_bytes())
+                    .await?,
+            )
+        };
+        let payload = htlc_redeem::HtlcRedeemParams {
+            prefix: Prefix::HtlcRedeem.to_bytes().to_vec(),
+            blockchain: format!("{:?}", self.lock.chain),
+            hash: hex::encode(&self.lock.hash.0),
+            preimage: hex::encode(&self.preimage.0),
+            signature,
+        };
+        Ok(graphql::HtlcRedeem::build_query(htlc_redeem::Variables { payload }))
+    }
+}
+
+impl HtlcRefundRequest {
+    /// Bytes signed for this request, in the binary form
+    /// `Signer::sign_payload` expects: the prefix and the hash. The
+    /// `blockchain` field that `make_query` also sends is not part of the
+    /// signed bytes.
+    fn payload_bytes(&self) -> Vec<u8> {
+        let mut bytes = Prefix::HtlcRefund.to_bytes().to_vec();
+        bytes.extend_from_slice(&self.lock.hash.0);
+        bytes
+    }
+
+    pub async fn make_query(
+        &self,
+        state: Arc<Mutex<State>>,
+    ) -> Result<graphql_client::QueryBody<htlc_refund::Variables>> {
+        let signature = {
+            let mut state = state.lock().await;
+            hex::encode(
+                state
+                    .signer()?
+                    .sign_payload(self.lock.chain, &self.payload_bytes())
+                    .await?,
+            )
+        };
+        let payload = htlc_refund::HtlcRefundParams {
+            prefix: Prefix::HtlcRefund.to_bytes().to_vec(),
+            blockchain: format!("{:?}", self.lock.chain),
+            hash: hex::encode(&self.lock.hash.0),
+            signature,
+        };
+        Ok(graphql::HtlcRefund::build_query(htlc_refund::Variables { payload }))
+    }
+}
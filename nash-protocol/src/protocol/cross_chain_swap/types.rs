@@ -0,0 +1,337 @@
+//! Cross-chain atomic swaps via hash-time-locked contracts (HTLCs), letting
+//! two parties trustlessly exchange assets across the blockchains Nash
+//! supports (Bitcoin/Ethereum on secp256k1, NEO on secp256r1) without a
+//! trusted intermediary. Mirrors the HTLC construction used by the XMR<->BTC
+//! and comit-rs swaps:
+//!
+//! - The initiator picks a secret preimage `s`, publishes `H = hash(s)`, and
+//!   locks funds redeemable by the initiator after timeout `t2`, or by the
+//!   responder with knowledge of `s`.
+//! - The responder locks the counter-asset redeemable by the responder after
+//!   timeout `t1 < t2`, or by the initiator with `s`.
+//! - The initiator claims the responder's lock first, revealing `s`
+//!   on-chain; the responder then uses the revealed `s` to claim the
+//!   initiator's lock.
+//!
+//! `t1 < t2` is the safety invariant that makes the swap atomic: it
+//! guarantees the responder always has time to claim the initiator's lock
+//! with `s` (or refund) before the initiator's own refund window opens.
+//! [`Swap::new`] enforces it at construction so an unsafe pairing of
+//! timeouts can't be built in the first place.
+
+use crate::errors::{ProtocolError, Result};
+use crate::graphql::{htlc_lock, htlc_redeem, htlc_refund};
+use crate::protocol::{
+    serializable_to_json, try_response_from_json, NashProtocol, ResponseOrError, State,
+};
+use crate::types::blockchain::Address;
+use crate::types::Blockchain;
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use std::sync::Arc;
+
+/// The secret value `s` that unlocks a [`HtlcLock`] before its timeout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preimage(pub [u8; 32]);
+
+/// `H = hash(s)`, published on-chain when a lock is created and checked
+/// against a revealed [`Preimage`] on redeem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hash(pub [u8; 32]);
+
+impl Preimage {
+    /// Hash this preimage the same way the on-chain contract does, so a
+    /// redeem can be checked locally before it's ever sent to the server.
+    pub fn hash(&self) -> Hash {
+        Hash(nash_mpc::common::sha256(&self.0))
+    }
+}
+
+/// Lifecycle of one leg of an HTLC swap.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HtlcState {
+    /// No on-chain transaction exists yet.
+    Init,
+    /// Funds are locked on-chain, redeemable by the counterparty with `s`
+    /// before the timeout, or refundable by the locker after.
+    Locked,
+    /// The counterparty redeemed the lock, revealing `s`.
+    Redeemed,
+    /// The timeout elapsed and the locker reclaimed their funds.
+    Refunded,
+}
+
+/// One leg of a two-chain atomic swap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtlcLock {
+    pub chain: Blockchain,
+    pub asset: crate::types::Asset,
+    pub amount: String,
+    /// Address that can reclaim the funds after `timeout` if unredeemed.
+    pub locker: Address,
+    /// Address that can redeem the funds before `timeout` by revealing `s`.
+    pub counterparty: Address,
+    pub hash: Hash,
+    /// Seconds after which `locker` may refund if unredeemed.
+    pub timeout: u64,
+    pub state: HtlcState,
+}
+
+impl HtlcLock {
+    pub fn new(
+        chain: Blockchain,
+        asset: crate::types::Asset,
+        amount: String,
+        locker: Address,
+        counterparty: Address,
+        hash: Hash,
+        timeout: u64,
+    ) -> Self {
+        Self {
+            chain,
+            asset,
+            amount,
+            locker,
+            counterparty,
+            hash,
+            timeout,
+            state: HtlcState::Init,
+        }
+    }
+}
+
+/// The full two-leg swap: the initiator's lock (claimable by the responder
+/// with `s`, refundable by the initiator after `t2`) and the responder's
+/// lock (claimable by the initiator with `s`, refundable by the responder
+/// after `t1`).
+#[derive(Clone, Debug)]
+pub struct Swap {
+    pub initiator_lock: HtlcLock,
+    pub responder_lock: HtlcLock,
+}
+
+impl Swap {
+    /// Pair up both legs of a swap, enforcing the `t1 < t2` timeout
+    /// invariant and that both legs commit to the same secret hash.
+    pub fn new(initiator_lock: HtlcLock, responder_lock: HtlcLock) -> Result<Self> {
+        if responder_lock.timeout >= initiator_lock.timeout {
+            return Err(ProtocolError::Other(
+                "responder timeout (t1) must be strictly before initiator timeout (t2)".to_string(),
+            ));
+        }
+        if initiator_lock.hash != responder_lock.hash {
+            return Err(ProtocolError::Crypto(
+                "both legs of a swap must commit to the same secret hash".to_string(),
+            ));
+        }
+        Ok(Self {
+            initiator_lock,
+            responder_lock,
+        })
+    }
+}
+
+/// Build and sign the payload that creates an on-chain HTLC lock for one leg
+/// of a swap.
+#[derive(Clone, Debug)]
+pub struct HtlcLockRequest {
+    pub lock: HtlcLock,
+}
+
+/// Build and sign the payload that redeems an HTLC lock by revealing the
+/// preimage `s`. Must only be submitted once `s` is known, since submitting
+/// it reveals `s` to everyone watching the chain.
+#[derive(Clone, Debug)]
+pub struct HtlcRedeemRequest {
+    pub lock: HtlcLock,
+    pub preimage: Preimage,
+}
+
+/// Build and sign the payload that refunds an HTLC lock after its timeout
+/// has elapsed. This is the safety-critical path: it must be available
+/// independent of the counterparty's cooperation, since the entire point of
+/// an HTLC is that a non-responsive counterparty cannot strand funds.
+#[derive(Clone, Debug)]
+pub struct HtlcRefundRequest {
+    pub lock: HtlcLock,
+}
+
+/// Server acknowledgement of a submitted lock/redeem/refund payload.
+#[derive(Clone, Debug)]
+pub struct HtlcResponse {
+    pub state: HtlcState,
+    /// Populated once the transaction is visible on-chain.
+    pub tx_hash: Option<String>,
+}
+
+#[async_trait]
+impl NashProtocol for HtlcLockRequest {
+    type Response = HtlcResponse;
+    async fn graphql(&self, state: Arc<Mutex<State>>) -> Result<serde_json::Value> {
+        serializable_to_json(&self.make_query(state).await?)
+    }
+    fn response_from_json(
+        &self,
+        response: serde_json::Value,
+    ) -> Result<ResponseOrError<Self::Response>> {
+        try_response_from_json::<HtlcResponse, htlc_lock::ResponseData>(response)
+    }
+    async fn process_response(
+        &self,
+        response: &Self::Response,
+        _state: Arc<Mutex<State>>,
+    ) -> Result<()> {
+        if response.state != HtlcState::Locked {
+            return Err(ProtocolError::UnexpectedResponse("expected lock confirmation from server".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NashProtocol for HtlcRedeemRequest {
+    type Response = HtlcResponse;
+    async fn graphql(&self, state: Arc<Mutex<State>>) -> Result<serde_json::Value> {
+        // Redeeming necessarily reveals `s`, so check it matches the hash
+        // this lock committed to *before* `make_query` hex-encodes it into
+        // the outgoing payload -- an invalid preimage must never go out
+        // over the wire, let alone only be caught after the fact.
+        if self.preimage.hash() != self.lock.hash {
+            return Err(ProtocolError::Crypto(
+                "preimage does not match the hash committed to by this lock".to_string(),
+            ));
+        }
+        serializable_to_json(&self.make_query(state).await?)
+    }
+    fn response_from_json(
+        &self,
+        response: serde_json::Value,
+    ) -> Result<ResponseOrError<Self::Response>> {
+        try_response_from_json::<HtlcResponse, htlc_redeem::ResponseData>(response)
+    }
+    async fn process_response(
+        &self,
+        response: &Self::Response,
+        _state: Arc<Mutex<State>>,
+    ) -> Result<()> {
+        if response.state != HtlcState::Redeemed {
+            return Err(ProtocolError::UnexpectedResponse("expected redeem confirmation from server".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NashProtocol for HtlcRefundRequest {
+    type Response = HtlcResponse;
+    async fn graphql(&self, state: Arc<Mutex<State>>) -> Result<serde_json::Value> {
+        serializable_to_json(&self.make_query(state).await?)
+    }
+    fn response_from_json(
+        &self,
+        response: serde_json::Value,
+    ) -> Result<ResponseOrError<Self::Response>> {
+        try_response_from_json::<HtlcResponse, htlc_refund::ResponseData>(response)
+    }
+    async fn process_response(
+        &self,
+        response: &Self::Response,
+        _state: Arc<Mutex<State>>,
+    ) -> Result<()> {
+        if response.state != HtlcState::Refunded {
+            return Err(ProtocolError::UnexpectedResponse("expected refund confirmation from server".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+
+    fn lock(timeout: u64, hash: Hash) -> HtlcLock {
+        HtlcLock::new(
+            Blockchain::Ethereum,
+            Asset::ETH,
+            "1".to_string(),
+            Address::new(
+                Blockchain::Ethereum,
+                "0x1111111111111111111111111111111111111111",
+            )
+            .unwrap(),
+            Address::new(
+                Blockchain::Ethereum,
+                "0x2222222222222222222222222222222222222222",
+            )
+            .unwrap(),
+            hash,
+            timeout,
+        )
+    }
+
+    #[test]
+    fn rejects_a_responder_timeout_that_is_not_strictly_before_the_initiators() {
+        let preimage = Preimage([7u8; 32]);
+        let hash = preimage.hash();
+
+        let err = Swap::new(lock(200, hash.clone()), lock(200, hash))
+            .expect_err("responder timeout equal to initiator's must be rejected");
+        assert!(matches!(err, ProtocolError::Other(_)));
+    }
+
+    #[test]
+    fn rejects_mismatched_secret_hashes() {
+        let preimage_a = Preimage([1u8; 32]);
+        let preimage_b = Preimage([2u8; 32]);
+
+        let err = Swap::new(
+            lock(200, preimage_a.hash()),
+            lock(100, preimage_b.hash()),
+        )
+        .expect_err("mismatched hashes across legs must be rejected");
+        assert!(matches!(err, ProtocolError::Crypto(_)));
+    }
+
+    #[test]
+    fn accepts_a_safe_pairing() {
+        let preimage = Preimage([9u8; 32]);
+        let hash = preimage.hash();
+
+        Swap::new(lock(200, hash.clone()), lock(100, hash)).unwrap();
+    }
+
+    #[test]
+    fn redeem_rejects_an_invalid_preimage_before_building_the_request() {
+        let correct = Preimage([3u8; 32]);
+        let wrong = Preimage([4u8; 32]);
+        let req = HtlcRedeemRequest {
+            lock: lock(100, correct.hash()),
+            preimage: wrong,
+        };
+
+        let state = Arc::new(Mutex::new(
+            State::new(Some("../nash-native-client/test_data/keyfile.json")).unwrap(),
+        ));
+        let result = futures::executor::block_on(req.graphql(state));
+        assert!(matches!(result, Err(ProtocolError::Crypto(_))));
+    }
+
+    #[test]
+    fn lock_request_signs_its_payload_before_sending_it() {
+        let preimage = Preimage([5u8; 32]);
+        let req = HtlcLockRequest {
+            lock: lock(100, preimage.hash()),
+        };
+        let state = Arc::new(Mutex::new(
+            State::new(Some("../nash-native-client/test_data/keyfile.json")).unwrap(),
+        ));
+
+        let query = futures::executor::block_on(req.graphql(state)).unwrap();
+        let signature = query["variables"]["payload"]["signature"]
+            .as_str()
+            .expect("signed query must carry a signature field");
+        assert!(!signature.is_empty());
+    }
+}
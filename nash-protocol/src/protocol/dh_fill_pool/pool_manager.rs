@@ -0,0 +1,278 @@
+//! Tracks per-[`Blockchain`] R-value consumption and refills the pool before
+//! it runs dry, analogous to the nonce-manager middleware in ethers-rs that
+//! transparently tracks and supplies the next nonce. `PoolManager` lives on
+//! `State`, so every check and update happens while holding the
+//! `futures::lock::Mutex<State>` callers already lock around a request --
+//! no separate lock is needed to coalesce concurrent refill triggers, since
+//! only one caller can be inspecting the manager at a time. A signing
+//! operation should call [`spend`] as it consumes R values, rather than
+//! touching [`PoolManager::record_consumed`] and [`ensure_filled`]
+//! separately.
+
+use super::DhFillPoolRequest;
+use crate::errors::{ProtocolError, Result};
+use crate::protocol::middleware::NashMiddleware;
+use crate::protocol::State;
+use crate::signer::Signer;
+use crate::types::Blockchain;
+
+use futures::lock::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Default number of remaining R values below which a refill is triggered.
+pub const DEFAULT_LOW_WATER: usize = 20;
+
+/// Per-blockchain R-value bookkeeping. [`spend`] calls
+/// [`record_consumed`](PoolManager::record_consumed) as a signing operation
+/// spends values, and a `DhFillPoolRequest`'s `process_response` should call
+/// [`record_filled`](PoolManager::record_filled) once the server confirms a
+/// refill.
+#[derive(Debug, Default)]
+pub struct PoolManager {
+    low_water: HashMap<Blockchain, usize>,
+    remaining: HashMap<Blockchain, usize>,
+    refill_in_flight: HashMap<Blockchain, bool>,
+}
+
+impl PoolManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configure the low-water threshold for `chain`. Defaults to
+    /// [`DEFAULT_LOW_WATER`] when not set.
+    pub fn set_low_water(&mut self, chain: Blockchain, threshold: usize) {
+        self.low_water.insert(chain, threshold);
+    }
+
+    fn low_water(&self, chain: Blockchain) -> usize {
+        *self.low_water.get(&chain).unwrap_or(&DEFAULT_LOW_WATER)
+    }
+
+    /// R values currently available for `chain`.
+    pub fn remaining(&self, chain: Blockchain) -> usize {
+        *self.remaining.get(&chain).unwrap_or(&0)
+    }
+
+    /// Record that a `DhFillPool` response added `count` new R values.
+    pub fn record_filled(&mut self, chain: Blockchain, count: usize) {
+        *self.remaining.entry(chain).or_insert(0) += count;
+        self.refill_in_flight.insert(chain, false);
+    }
+
+    /// Clear `chain`'s in-flight marker after an attempted refill failed
+    /// (network error, GraphQL error, bad response), so the next
+    /// `should_refill` call can trigger another attempt instead of being
+    /// wedged forever -- only a *successful* refill reaches
+    /// [`record_filled`] to clear it otherwise.
+    fn refill_failed(&mut self, chain: Blockchain) {
+        self.refill_in_flight.insert(chain, false);
+    }
+
+    /// Record that a signing operation spent `count` R values, failing with
+    /// a typed error if fewer than `count` were available to spend.
+    pub fn record_consumed(&mut self, chain: Blockchain, count: usize) -> Result<()> {
+        if count > self.remaining(chain) {
+            return Err(ProtocolError::PoolExhausted(format!(
+                "only {} r values remaining for {:?}, but {} were consumed",
+                self.remaining(chain),
+                chain,
+                count
+            )));
+        }
+        let entry = self.remaining.entry(chain).or_insert(0);
+        *entry -= count;
+        Ok(())
+    }
+
+    /// Returns `true` when `chain`'s pool has dropped below its low-water
+    /// mark and no refill is already in flight. Marks a refill as in flight
+    /// so a burst of concurrent callers (all holding the same `State` lock
+    /// in turn) only trigger one; it fires again once that refill is
+    /// recorded as filled or as failed.
+    fn should_refill(&mut self, chain: Blockchain) -> bool {
+        let below_threshold = self.remaining(chain) < self.low_water(chain);
+        let already_in_flight = *self.refill_in_flight.get(&chain).unwrap_or(&false);
+        if below_threshold && !already_in_flight {
+            self.refill_in_flight.insert(chain, true);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Check whether `chain`'s pool needs topping up and, if so, run a
+/// `DhFillPoolRequest` through `stack`, generating the refill's secrets via
+/// `signer` rather than in-process -- the same `Signer` a signing operation
+/// already has to have on hand to spend the values in the first place.
+/// [`spend`] calls this right after recording what was consumed.
+///
+/// A refill built this way does not complete: `DhFillPoolRequest`'s
+/// `process_response` returns an error for every request made with
+/// `new_with_signer`, so when a refill is triggered this returns that error,
+/// no R values are added, and the in-flight marker is cleared.
+pub async fn ensure_filled<M: NashMiddleware>(
+    chain: Blockchain,
+    signer: &dyn Signer,
+    stack: &M,
+    state: Arc<Mutex<State>>,
+) -> Result<()> {
+    let should_refill = {
+        let mut locked = state.lock().await;
+        locked.pool_manager().should_refill(chain)
+    };
+    if should_refill {
+        let req = DhFillPoolRequest::new_with_signer(chain, signer)?;
+        if let Err(err) = stack.run(req, state.clone()).await {
+            // Don't leave the next caller permanently wedged because this
+            // attempt happened to fail -- clear the in-flight marker so a
+            // later `should_refill` can try again.
+            state.lock().await.pool_manager().refill_failed(chain);
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Record that a signing operation is about to spend `count` R values for
+/// `chain`, then top up the pool through `stack` if that drops it below its
+/// low-water mark. This is the entry point a signing operation should call
+/// instead of reaching into `PoolManager::record_consumed` and
+/// [`ensure_filled`] separately.
+pub async fn spend<M: NashMiddleware>(
+    chain: Blockchain,
+    count: usize,
+    signer: &dyn Signer,
+    stack: &M,
+    state: Arc<Mutex<State>>,
+) -> Result<()> {
+    {
+        let mut locked = state.lock().await;
+        locked.pool_manager().record_consumed(chain, count)?;
+    }
+    ensure_filled(chain, signer, stack, state).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::middleware::{GraphqlExecutor, Transport};
+    use crate::signer::keyfile::KeyfileSigner;
+
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails every call, counting how many times it was asked to run.
+    #[derive(Debug)]
+    struct FailingExecutor {
+        calls: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl GraphqlExecutor for FailingExecutor {
+        async fn execute(&self, _query: serde_json::Value) -> Result<serde_json::Value> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(ProtocolError::Transport("unreachable".to_string()))
+        }
+    }
+
+    #[test]
+    fn a_failed_refill_clears_the_in_flight_flag_so_a_retry_can_happen() {
+        let state = Arc::new(Mutex::new(
+            State::new(Some("../nash-native-client/test_data/keyfile.json")).unwrap(),
+        ));
+        let signer =
+            KeyfileSigner::from_file("../nash-native-client/test_data/keyfile.json").unwrap();
+        let calls = Arc::new(AtomicU32::new(0));
+        let stack = Transport::new(FailingExecutor {
+            calls: calls.clone(),
+        });
+
+        let first = futures::executor::block_on(ensure_filled(
+            Blockchain::Ethereum,
+            &signer,
+            &stack,
+            state.clone(),
+        ));
+        assert!(first.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let second = futures::executor::block_on(ensure_filled(
+            Blockchain::Ethereum,
+            &signer,
+            &stack,
+            state.clone(),
+        ));
+        assert!(second.is_err());
+        // If the in-flight flag hadn't been cleared after the first
+        // failure, `should_refill` would now return `false` and the
+        // executor would never see a second call.
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn consuming_past_the_low_water_mark_triggers_exactly_one_refill() {
+        let mut manager = PoolManager::new();
+        manager.set_low_water(Blockchain::Ethereum, 5);
+        manager.record_filled(Blockchain::Ethereum, 10);
+
+        // Still above the low-water mark.
+        manager.record_consumed(Blockchain::Ethereum, 4).unwrap();
+        assert!(!manager.should_refill(Blockchain::Ethereum));
+
+        // Drops below it.
+        manager.record_consumed(Blockchain::Ethereum, 2).unwrap();
+        assert!(manager.should_refill(Blockchain::Ethereum));
+
+        // A burst of concurrent callers only sees the trigger once, since a
+        // refill is now in flight.
+        assert!(!manager.should_refill(Blockchain::Ethereum));
+        assert!(!manager.should_refill(Blockchain::Ethereum));
+    }
+
+    #[test]
+    fn a_completed_refill_allows_should_refill_to_fire_again() {
+        let mut manager = PoolManager::new();
+        manager.set_low_water(Blockchain::Bitcoin, 5);
+        manager.record_filled(Blockchain::Bitcoin, 3);
+
+        assert!(manager.should_refill(Blockchain::Bitcoin));
+        assert!(!manager.should_refill(Blockchain::Bitcoin));
+
+        manager.record_filled(Blockchain::Bitcoin, 100);
+        assert!(!manager.should_refill(Blockchain::Bitcoin));
+
+        manager.record_consumed(Blockchain::Bitcoin, 99).unwrap();
+        assert!(manager.should_refill(Blockchain::Bitcoin));
+    }
+
+    #[test]
+    fn consuming_from_an_empty_pool_is_a_typed_error() {
+        let mut manager = PoolManager::new();
+        let err = manager
+            .record_consumed(Blockchain::NEO, 1)
+            .expect_err("pool has no r values yet");
+        assert!(matches!(err, ProtocolError::PoolExhausted(_)));
+    }
+
+    #[test]
+    fn consuming_more_than_what_remains_is_a_typed_error_not_a_silent_clamp() {
+        let mut manager = PoolManager::new();
+        manager.record_filled(Blockchain::NEO, 1);
+
+        let err = manager
+            .record_consumed(Blockchain::NEO, 5)
+            .expect_err("only 1 r value remains, 5 were requested");
+        assert!(matches!(err, ProtocolError::PoolExhausted(_)));
+        // The failed overspend must not have touched the count either.
+        assert_eq!(manager.remaining(Blockchain::NEO), 1);
+    }
+
+    #[test]
+    fn low_water_defaults_when_unset() {
+        let manager = PoolManager::new();
+        assert_eq!(manager.low_water(Blockchain::Ethereum), DEFAULT_LOW_WATER);
+    }
+}
@@ -4,6 +4,7 @@ use super::super::{
 use super::response;
 use crate::errors::{ProtocolError, Result};
 use crate::graphql::dh_fill_pool;
+use crate::signer::{FillPoolPublics, Signer};
 use crate::types::Blockchain;
 use nash_mpc::curves::secp256_k1::{Secp256k1Point, Secp256k1Scalar};
 use nash_mpc::curves::secp256_r1::{Secp256r1Point, Secp256r1Scalar};
@@ -17,9 +18,11 @@ use std::sync::Arc;
 /// Points to the Nash server. The server sends back its own list of public Points.
 /// Both parties then multply the public point by the secret value to construct the
 /// same shared secret value (diffie-hellman). Bitcoin and Ethereum both use the
-/// Secp256k1 curve, while NEO users the Secp256r1 curve. While this request type
-/// holds both the secret and the public values, only the public values are used in
-/// creating the GraphQL request. The secrets are used to process a response.
+/// Secp256k1 curve, while NEO users the Secp256r1 curve. This request type
+/// holds the public values and, when generated in-process via `new`, the
+/// secrets too -- only the public values are used in creating the GraphQL
+/// request, and the secrets are used to process a response. `new_with_signer`
+/// leaves the secrets behind the `Signer` instead; see `K1FillPool::secrets`.
 #[derive(Clone, Debug)]
 pub enum DhFillPoolRequest {
     Bitcoin(K1FillPool),
@@ -36,6 +39,39 @@ impl DhFillPoolRequest {
             Blockchain::NEO => Ok(Self::NEO(R1FillPool::new()?)),
         }
     }
+    /// Create a new DhFillPool request whose secrets are generated and kept
+    /// by `signer` rather than in-process: `signer.generate_pool_publics`
+    /// returns only the public half, so a hardware wallet (or any other
+    /// `Signer` implementation) never has to hand its secrets to this
+    /// process. `process_response` calls the matching
+    /// `Signer::combine_pool_secrets` once the server's publics come back --
+    /// see the `signer` module docs.
+    pub fn new_with_signer(chain: Blockchain, signer: &dyn Signer) -> Result<Self> {
+        match signer.generate_pool_publics(chain, 100)? {
+            FillPoolPublics::K1(publics) => {
+                let pool = K1FillPool {
+                    publics,
+                    secrets: None,
+                };
+                match chain {
+                    Blockchain::Ethereum => Ok(Self::Ethereum(pool)),
+                    Blockchain::Bitcoin => Ok(Self::Bitcoin(pool)),
+                    Blockchain::NEO => Err(ProtocolError::Crypto(
+                        "Signer returned k1 publics for a r1 chain".to_string(),
+                    )),
+                }
+            }
+            FillPoolPublics::R1(publics) => match chain {
+                Blockchain::NEO => Ok(Self::NEO(R1FillPool {
+                    publics,
+                    secrets: None,
+                })),
+                _ => Err(ProtocolError::Crypto(
+                    "Signer returned r1 publics for a k1 chain".to_string(),
+                )),
+            },
+        }
+    }
     /// Get blockchain assocaited with DH request
     pub fn blockchain(&self) -> Blockchain {
         match self {
@@ -44,20 +80,38 @@ impl DhFillPoolRequest {
             Self::NEO(_) => Blockchain::NEO,
         }
     }
+
+    /// Whether this request's secrets were generated via `new_with_signer`
+    /// and so stay behind the `Signer` rather than traveling with `self` --
+    /// see [`K1FillPool::secrets`].
+    fn is_signer_backed(&self) -> bool {
+        match self {
+            Self::Bitcoin(pool) | Self::Ethereum(pool) => pool.secrets.is_none(),
+            Self::NEO(pool) => pool.secrets.is_none(),
+        }
+    }
 }
 
 /// Values for k1 curve (Bitcoin and Ethereum)
 #[derive(Clone, Debug)]
 pub struct K1FillPool {
     pub publics: Vec<Secp256k1Point>,
-    pub secrets: Vec<Secp256k1Scalar>,
+    /// `None` when generated via `Signer::generate_pool_publics` (see
+    /// `DhFillPoolRequest::new_with_signer`): the secrets stay behind the
+    /// `Signer` rather than travel with this request, so `process_response`
+    /// must go through `Signer::combine_pool_secrets` instead of the local
+    /// combination `response::fill_pool` does for the `Some` case.
+    pub secrets: Option<Vec<Secp256k1Scalar>>,
 }
 
 impl K1FillPool {
     pub fn new() -> Result<Self> {
         let (secrets, publics) = nash_mpc::common::dh_init_secp256k1(100)
-            .map_err(|_| ProtocolError("Could not initialize k1 values"))?;
-        Ok(Self { publics, secrets })
+            .map_err(|_| ProtocolError::Crypto("Could not initialize k1 values".to_string()))?;
+        Ok(Self {
+            publics,
+            secrets: Some(secrets),
+        })
     }
 }
 
@@ -65,14 +119,18 @@ impl K1FillPool {
 #[derive(Clone, Debug)]
 pub struct R1FillPool {
     pub publics: Vec<Secp256r1Point>,
-    pub secrets: Vec<Secp256r1Scalar>,
+    /// See [`K1FillPool::secrets`].
+    pub secrets: Option<Vec<Secp256r1Scalar>>,
 }
 
 impl R1FillPool {
     pub fn new() -> Result<Self> {
         let (secrets, publics) = nash_mpc::common::dh_init_secp256r1(100)
-            .map_err(|_| ProtocolError("Could not initialize r1 values"))?;
-        Ok(Self { publics, secrets })
+            .map_err(|_| ProtocolError::Crypto("Could not initialize r1 values".to_string()))?;
+        Ok(Self {
+            publics,
+            secrets: Some(secrets),
+        })
     }
 }
 
@@ -91,6 +149,20 @@ pub enum ServerPublics {
     NEO(Vec<Secp256r1Point>),
 }
 
+impl ServerPublics {
+    /// Curve-keyed view of these publics, matching `FillPoolPublics`'s shape
+    /// -- `Signer::combine_pool_secrets` is keyed by curve (k1 vs r1), not by
+    /// individual chain, since Bitcoin and Ethereum share the k1 curve.
+    fn to_fill_pool_publics(&self) -> FillPoolPublics {
+        match self {
+            Self::Bitcoin(publics) | Self::Ethereum(publics) => {
+                FillPoolPublics::K1(publics.clone())
+            }
+            Self::NEO(publics) => FillPoolPublics::R1(publics.clone()),
+        }
+    }
+}
+
 #[async_trait]
 impl NashProtocol for DhFillPoolRequest {
     type Response = DhFillPoolResponse;
@@ -113,10 +185,33 @@ impl NashProtocol for DhFillPoolRequest {
         state: Arc<Mutex<State>>,
     ) -> Result<()> {
         let server_publics = ServerPublics::from_hexstrings(self.blockchain(), response)?;
+        if self.is_signer_backed() {
+            // Built via `new_with_signer`: no local secrets to combine with,
+            // so ask the `Signer` to do the DH multiplication it retained
+            // from `generate_pool_publics` instead of reading a `secrets`
+            // field off `self`. See `K1FillPool::secrets` and the `signer`
+            // module docs.
+            let mut locked = state.lock().await;
+            let _shared = locked
+                .signer()?
+                .combine_pool_secrets(self.blockchain(), &server_publics.to_fill_pool_publics())?;
+            // There's nowhere to put `_shared` yet: `response::fill_pool`
+            // below is what writes combined secrets into the R-value pool,
+            // and it only knows how to read them off a request's own
+            // `secrets` field, not take an already-combined `SharedSecrets`
+            // directly. Reporting this refill as filled without storing
+            // anything would desync `PoolManager`/`Signer` bookkeeping from
+            // what's actually available to sign with, so until storage is
+            // wired up this has to fail loudly instead.
+            return Err(ProtocolError::Other(
+                "signer-backed DhFillPool refill computed its shared secrets but has no path to persist them yet; treat this refill as failed".to_string(),
+            ));
+        }
         response::fill_pool(self, server_publics, state.clone()).await?;
         let mut state = state.lock().await;
         // Update state to indicate we now have 100 new r values
         state.signer()?.fill_r_vals(self.blockchain(), 100);
+        state.pool_manager().record_filled(self.blockchain(), 100);
         Ok(())
     }
 }
@@ -0,0 +1,37 @@
+use super::{ChainWatcher, Claim};
+use crate::errors::{ProtocolError, Result};
+
+use async_trait::async_trait;
+
+/// Watches Bitcoin transaction confirmations for a deposit or withdrawal,
+/// polling until the transaction has `claim.confirmations_required`
+/// confirmations.
+#[derive(Debug)]
+pub struct BitcoinWatcher {
+    /// Bitcoin Core (or Electrum) RPC endpoint used to fetch transaction
+    /// confirmation counts.
+    rpc_url: String,
+}
+
+impl BitcoinWatcher {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for BitcoinWatcher {
+    /// Fetch the current confirmation count for `claim`'s movement, if the
+    /// transaction is visible in the mempool or a block yet. See the
+    /// `eventuality` module docs for why this is unconditional for now.
+    async fn poll_once(&self, _claim: &Claim) -> Result<Option<(String, u64)>> {
+        // Needs a `gettransaction`/`getrawtransaction` RPC client against
+        // `rpc_url`, which this crate doesn't have yet.
+        Err(ProtocolError::Transport(format!(
+            "BitcoinWatcher has no RPC client wired up to {}",
+            self.rpc_url
+        )))
+    }
+}
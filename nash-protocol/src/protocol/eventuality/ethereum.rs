@@ -0,0 +1,37 @@
+use super::{ChainWatcher, Claim};
+use crate::errors::{ProtocolError, Result};
+
+use async_trait::async_trait;
+
+/// Watches Ethereum logs/receipts for a deposit or withdrawal, polling until
+/// the transaction has `claim.confirmations_required` block confirmations.
+#[derive(Debug)]
+pub struct EthereumWatcher {
+    /// JSON-RPC endpoint used to fetch receipts and the current block
+    /// height.
+    rpc_url: String,
+}
+
+impl EthereumWatcher {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for EthereumWatcher {
+    /// Fetch the receipt for `claim`'s movement, if it has been mined, along
+    /// with its current confirmation count (`eth_blockNumber` minus the
+    /// receipt's block number). See the `eventuality` module docs for why
+    /// this is unconditional for now.
+    async fn poll_once(&self, _claim: &Claim) -> Result<Option<(String, u64)>> {
+        // Needs an `eth_getTransactionReceipt` + `eth_blockNumber` JSON-RPC
+        // client against `rpc_url`, which this crate doesn't have yet.
+        Err(ProtocolError::Transport(format!(
+            "EthereumWatcher has no JSON-RPC client wired up to {}",
+            self.rpc_url
+        )))
+    }
+}
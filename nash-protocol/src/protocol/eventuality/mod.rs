@@ -0,0 +1,191 @@
+//! On-chain finality tracking for submitted deposits and withdrawals.
+//! `MovementType::Deposit`/`Withdrawal` produce signed payloads, but nothing
+//! today confirms a movement actually settled on-chain -- callers have to
+//! assume success once the GraphQL call returns. This module is a watcher
+//! subsystem, inspired by comit-rs's btsieve block scanning and Serai's
+//! modular `Eventuality`/`confirm_completion` abstraction, that polls the
+//! relevant chain until a configurable confirmation depth is reached and
+//! resolves with the finalized transaction.
+//!
+//! **Reduced scope.** This delivers the shared watcher framework --
+//! [`ChainWatcher::watch`]'s poll-until-confirmed loop, [`Claim`]/
+//! [`Completion`], and [`ChainWatchers`]'s per-chain dispatch -- but not a
+//! working integration: [`bitcoin::BitcoinWatcher`],
+//! [`ethereum::EthereumWatcher`], and [`neo::NeoWatcher`] each need an RPC
+//! client this crate doesn't carry (Bitcoin Core/Electrum, Ethereum
+//! JSON-RPC, NEO RPC), so their `poll_once` is unconditionally an error, and
+//! no movement request's `process_response` calls `ChainWatchers::watch`
+//! yet since that wiring belongs in the deposit/withdrawal movement module.
+//! Plugging in a real RPC client per chain and that one call site is what's
+//! left for this to actually confirm anything on-chain.
+
+pub mod bitcoin;
+pub mod ethereum;
+pub mod neo;
+
+use crate::errors::Result;
+use crate::types::blockchain::{Address, Prefix};
+use crate::types::Blockchain;
+
+use async_trait::async_trait;
+use futures_timer::Delay;
+use std::time::Duration;
+
+/// A movement awaiting on-chain finality, keyed the same way the signed
+/// payload itself is: by its [`Prefix`] (deposit vs. withdrawal) and
+/// destination [`Address`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Claim {
+    pub chain: Blockchain,
+    pub prefix: Prefix,
+    pub destination: Address,
+    /// Number of confirmations required before the movement is considered
+    /// final.
+    pub confirmations_required: u64,
+}
+
+/// The finalized transaction for a [`Claim`], once it has reached the
+/// required confirmation depth.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Completion {
+    pub claim: Claim,
+    pub tx_hash: String,
+    pub confirmations: u64,
+}
+
+/// Polls a single blockchain for the finality of submitted movements.
+/// Implemented per chain since each has its own notion of confirmation
+/// (block depth for Bitcoin/NEO, receipt + block depth for Ethereum) -- but
+/// the poll-until-confirmed loop itself is identical across chains, so it
+/// lives once here as a default [`watch`](ChainWatcher::watch) rather than
+/// being copied into every chain's module.
+#[async_trait]
+pub trait ChainWatcher: Send + Sync {
+    /// Fetch the current confirmation count for `claim`'s movement, if the
+    /// transaction is visible on-chain yet. `None` means it hasn't appeared
+    /// yet (still in the mempool, or not broadcast).
+    async fn poll_once(&self, claim: &Claim) -> Result<Option<(String, u64)>>;
+
+    /// Poll until `claim` reaches its required confirmation depth, sleeping
+    /// `poll_interval` between checks.
+    async fn watch(&self, claim: Claim, poll_interval: Duration) -> Result<Completion> {
+        loop {
+            if let Some((tx_hash, confirmations)) = self.poll_once(&claim).await? {
+                if confirmations >= claim.confirmations_required {
+                    return Ok(Completion {
+                        claim,
+                        tx_hash,
+                        confirmations,
+                    });
+                }
+            }
+            Delay::new(poll_interval).await;
+        }
+    }
+}
+
+/// Holds one configured watcher per chain and dispatches a [`Claim`] to the
+/// right one based on `claim.chain`.
+#[derive(Debug)]
+pub struct ChainWatchers {
+    pub ethereum: ethereum::EthereumWatcher,
+    pub bitcoin: bitcoin::BitcoinWatcher,
+    pub neo: neo::NeoWatcher,
+}
+
+impl ChainWatchers {
+    pub fn new(
+        ethereum: ethereum::EthereumWatcher,
+        bitcoin: bitcoin::BitcoinWatcher,
+        neo: neo::NeoWatcher,
+    ) -> Self {
+        Self {
+            ethereum,
+            bitcoin,
+            neo,
+        }
+    }
+
+    /// Watch `claim` to completion using the watcher for its chain.
+    pub async fn watch(&self, claim: Claim, poll_interval: Duration) -> Result<Completion> {
+        match claim.chain {
+            Blockchain::Ethereum => self.ethereum.watch(claim, poll_interval).await,
+            Blockchain::Bitcoin => self.bitcoin.watch(claim, poll_interval).await,
+            Blockchain::NEO => self.neo.watch(claim, poll_interval).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ProtocolError;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn claim(confirmations_required: u64) -> Claim {
+        Claim {
+            chain: Blockchain::Ethereum,
+            prefix: Prefix::Deposit,
+            destination: Address::new(
+                Blockchain::Ethereum,
+                "0x1111111111111111111111111111111111111111",
+            )
+            .unwrap(),
+            confirmations_required,
+        }
+    }
+
+    /// Reports no transaction for its first poll, then one with an
+    /// increasing confirmation count on every poll after.
+    #[derive(Debug, Default)]
+    struct FakeWatcher {
+        polls: AtomicU64,
+    }
+
+    #[async_trait]
+    impl ChainWatcher for FakeWatcher {
+        async fn poll_once(&self, _claim: &Claim) -> Result<Option<(String, u64)>> {
+            let poll = self.polls.fetch_add(1, Ordering::SeqCst);
+            Ok(match poll {
+                0 => None,
+                n => Some(("0xabc".to_string(), n)),
+            })
+        }
+    }
+
+    #[test]
+    fn watch_polls_until_the_required_confirmation_depth_is_reached() {
+        let watcher = FakeWatcher::default();
+
+        let completion = futures::executor::block_on(
+            watcher.watch(claim(3), Duration::from_millis(1)),
+        )
+        .unwrap();
+
+        assert_eq!(completion.tx_hash, "0xabc");
+        assert_eq!(completion.confirmations, 3);
+        // Polled once for the `None` (not yet visible) response, then again
+        // each time the confirmation count was still below the threshold
+        // (1 and 2), plus the final poll that reached it (3).
+        assert_eq!(watcher.polls.load(Ordering::SeqCst), 4);
+    }
+
+    #[derive(Debug, Default)]
+    struct FailingWatcher;
+
+    #[async_trait]
+    impl ChainWatcher for FailingWatcher {
+        async fn poll_once(&self, _claim: &Claim) -> Result<Option<(String, u64)>> {
+            Err(ProtocolError::Transport("rpc unreachable".to_string()))
+        }
+    }
+
+    #[test]
+    fn watch_propagates_a_poll_error_instead_of_retrying_forever() {
+        let err = futures::executor::block_on(
+            FailingWatcher.watch(claim(1), Duration::from_millis(1)),
+        )
+        .expect_err("a failed poll must not be swallowed");
+        assert!(matches!(err, ProtocolError::Transport(_)));
+    }
+}
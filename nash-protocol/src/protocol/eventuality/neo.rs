@@ -0,0 +1,36 @@
+use super::{ChainWatcher, Claim};
+use crate::errors::{ProtocolError, Result};
+
+use async_trait::async_trait;
+
+/// Watches NEO application logs for a deposit or withdrawal, polling until
+/// the transaction's block has `claim.confirmations_required` confirmations.
+#[derive(Debug)]
+pub struct NeoWatcher {
+    /// NEO RPC endpoint used to fetch application logs and the current
+    /// block height.
+    rpc_url: String,
+}
+
+impl NeoWatcher {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ChainWatcher for NeoWatcher {
+    /// Fetch the application log for `claim`'s movement, if its transaction
+    /// has executed, along with its current confirmation count. See the
+    /// `eventuality` module docs for why this is unconditional for now.
+    async fn poll_once(&self, _claim: &Claim) -> Result<Option<(String, u64)>> {
+        // Needs a `getapplicationlog` + `getblockcount` RPC client against
+        // `rpc_url`, which this crate doesn't have yet.
+        Err(ProtocolError::Transport(format!(
+            "NeoWatcher has no RPC client wired up to {}",
+            self.rpc_url
+        )))
+    }
+}
@@ -0,0 +1,75 @@
+use super::{GraphqlExecutor, NashMiddleware, NashProtocol, ResponseOrError, State};
+use crate::errors::Result;
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use std::sync::Arc;
+
+/// Logs the outcome of each run through the stack, at `debug` level so it
+/// stays quiet unless a caller opts in via `RUST_LOG`. Deliberately does not
+/// log the serialized query itself: `NashProtocol::graphql()` is allowed to
+/// do real work while building it (e.g. consuming a single-use R value to
+/// sign a payload), so calling it a second time here just to log it would
+/// silently burn pool resources or sign twice. Wrap the base executor with
+/// [`LoggingExecutor`] instead if you also want the query logged -- it sees
+/// the query `Transport` already built, not a fresh one.
+#[derive(Debug)]
+pub struct LoggingMiddleware<M> {
+    inner: M,
+}
+
+impl<M: NashMiddleware> LoggingMiddleware<M> {
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<M: NashMiddleware> NashMiddleware for LoggingMiddleware<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn run<T>(
+        &self,
+        req: T,
+        state: Arc<Mutex<State>>,
+    ) -> Result<ResponseOrError<T::Response>>
+    where
+        T: NashProtocol + Clone + Send + Sync + 'static,
+    {
+        let result = self.inner().run(req, state).await;
+        match &result {
+            Ok(_) => log::debug!("nash protocol request succeeded"),
+            Err(err) => log::debug!("nash protocol request failed: {}", err),
+        }
+        result
+    }
+}
+
+/// Wraps a [`GraphqlExecutor`] to log the serialized query it's asked to
+/// send, at `debug` level. Lives at the executor layer rather than as a
+/// `NashMiddleware` so it sees the query exactly once -- the single time
+/// `Transport` calls `NashProtocol::graphql()` to build it -- instead of
+/// invoking `graphql()` itself and risking a second, possibly stateful,
+/// call.
+#[derive(Debug)]
+pub struct LoggingExecutor<E> {
+    inner: E,
+}
+
+impl<E: GraphqlExecutor> LoggingExecutor<E> {
+    pub fn new(inner: E) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<E: GraphqlExecutor> GraphqlExecutor for LoggingExecutor<E> {
+    async fn execute(&self, query: serde_json::Value) -> Result<serde_json::Value> {
+        log::debug!("nash protocol request: {}", query);
+        self.inner.execute(query).await
+    }
+}
@@ -0,0 +1,183 @@
+use super::{NashMiddleware, NashProtocol, ResponseOrError, State};
+use crate::errors::Result;
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Counts requests, successes, and failures seen by the stack. Exposed as
+/// plain atomics rather than wired into a specific metrics backend (e.g.
+/// Prometheus), so callers can scrape [`counts`](MetricsMiddleware::counts)
+/// on whatever cadence suits them.
+#[derive(Debug, Default)]
+pub struct MetricsMiddleware<M> {
+    inner: M,
+    requests: AtomicU64,
+    successes: AtomicU64,
+    failures: AtomicU64,
+}
+
+/// Snapshot of the counters tracked by [`MetricsMiddleware`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Counts {
+    pub requests: u64,
+    pub successes: u64,
+    pub failures: u64,
+}
+
+impl<M: NashMiddleware> MetricsMiddleware<M> {
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            requests: AtomicU64::new(0),
+            successes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+        }
+    }
+
+    pub fn counts(&self) -> Counts {
+        Counts {
+            requests: self.requests.load(Ordering::Relaxed),
+            successes: self.successes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[async_trait]
+impl<M: NashMiddleware> NashMiddleware for MetricsMiddleware<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn run<T>(
+        &self,
+        req: T,
+        state: Arc<Mutex<State>>,
+    ) -> Result<ResponseOrError<T::Response>>
+    where
+        T: NashProtocol + Clone + Send + Sync + 'static,
+    {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let result = self.inner().run(req, state).await;
+        match &result {
+            Ok(_) => self.successes.fetch_add(1, Ordering::Relaxed),
+            Err(_) => self.failures.fetch_add(1, Ordering::Relaxed),
+        };
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::dh_fill_pool::DhFillPoolRequest;
+    use crate::protocol::middleware::{GraphqlExecutor, Transport};
+    use crate::types::Blockchain;
+
+    #[derive(Debug)]
+    struct OkExecutor;
+
+    #[async_trait]
+    impl GraphqlExecutor for OkExecutor {
+        async fn execute(&self, _query: serde_json::Value) -> Result<serde_json::Value> {
+            Ok(serde_json::json!({}))
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingExecutor;
+
+    #[async_trait]
+    impl GraphqlExecutor for FailingExecutor {
+        async fn execute(&self, _query: serde_json::Value) -> Result<serde_json::Value> {
+            Err(crate::errors::ProtocolError::Transport("unreachable".to_string()))
+        }
+    }
+
+    /// A `NashProtocol` that always succeeds without touching `State`, so a
+    /// successful `run` can be tested without depending on how a real
+    /// request's `response_from_json` parses a canned response.
+    #[derive(Clone, Debug)]
+    struct NoopRequest;
+
+    #[async_trait]
+    impl NashProtocol for NoopRequest {
+        type Response = ();
+
+        async fn graphql(&self, _state: Arc<Mutex<State>>) -> Result<serde_json::Value> {
+            Ok(serde_json::json!({}))
+        }
+
+        fn response_from_json(
+            &self,
+            _response: serde_json::Value,
+        ) -> Result<ResponseOrError<Self::Response>> {
+            Ok(ResponseOrError::Response(()))
+        }
+
+        async fn process_response(
+            &self,
+            _response: &Self::Response,
+            _state: Arc<Mutex<State>>,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn state() -> Arc<Mutex<State>> {
+        Arc::new(Mutex::new(
+            State::new(Some("../nash-native-client/test_data/keyfile.json")).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn counts_start_at_zero() {
+        let stack = MetricsMiddleware::new(Transport::new(OkExecutor));
+        assert_eq!(
+            stack.counts(),
+            Counts {
+                requests: 0,
+                successes: 0,
+                failures: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn a_successful_run_increments_requests_and_successes_only() {
+        let stack = MetricsMiddleware::new(Transport::new(OkExecutor));
+
+        futures::executor::block_on(stack.run(NoopRequest, state())).unwrap();
+
+        assert_eq!(
+            stack.counts(),
+            Counts {
+                requests: 1,
+                successes: 1,
+                failures: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn a_failed_run_increments_requests_and_failures_only() {
+        let stack = MetricsMiddleware::new(Transport::new(FailingExecutor));
+        let req = DhFillPoolRequest::new(Blockchain::Ethereum).unwrap();
+
+        let result = futures::executor::block_on(stack.run(req, state()));
+
+        assert!(result.is_err());
+        assert_eq!(
+            stack.counts(),
+            Counts {
+                requests: 1,
+                successes: 0,
+                failures: 1,
+            }
+        );
+    }
+}
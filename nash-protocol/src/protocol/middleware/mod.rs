@@ -0,0 +1,123 @@
+//! Composable middleware for running [`NashProtocol`] requests, mirroring the
+//! `Provider` -> `Middleware` layering used by ethers-rs. Each layer wraps an
+//! `Inner` middleware (another layer, or ultimately a [`Transport`]) and may
+//! inspect the typed request and its parsed response, short-circuit with a
+//! response of its own, or retry on a [`ProtocolError`](crate::errors::ProtocolError)
+//! before delegating further down the stack. A layer does not see the
+//! serialized `serde_json::Value` query or response; only a
+//! [`GraphqlExecutor`] wrapper (such as [`logging::LoggingExecutor`]) does,
+//! and it can read or change them. The base of every stack performs
+//! the `graphql()` / `response_from_json()` / `process_response()` round trip
+//! that `NashProtocol` implementors relied on directly before middleware
+//! existed, so adding cross-cutting behavior (retries, rate limiting, request
+//! logging, metrics) no longer means patching every protocol request.
+//!
+//! ```ignore
+//! let stack = Transport::new(LoggingExecutor::new(executor))
+//!     .wrap_into(RetryMiddleware::new)
+//!     .wrap_into(|m| RateLimitMiddleware::new(m, 10))
+//!     .wrap_into(LoggingMiddleware::new);
+//! stack.run(DhFillPoolRequest::new(Blockchain::Ethereum)?, state).await?;
+//! ```
+
+pub mod logging;
+pub mod metrics;
+pub mod rate_limit;
+pub mod retry;
+
+use super::{NashProtocol, ResponseOrError, State};
+use crate::errors::Result;
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A single layer in a middleware stack. Implementors hold an `Inner`
+/// middleware and override [`run`](NashMiddleware::run) to add behavior
+/// around it, delegating to `self.inner().run(..)` to continue the chain.
+/// The default `run` simply forwards to `Inner`, so a layer only needs to
+/// override what it actually changes.
+#[async_trait]
+pub trait NashMiddleware: Send + Sync + Debug {
+    /// The middleware (or transport) this layer wraps.
+    type Inner: NashMiddleware;
+
+    /// Access the wrapped middleware.
+    fn inner(&self) -> &Self::Inner;
+
+    /// Run `req` through this layer and the remainder of the stack. `state`
+    /// is threaded through unchanged so layers can read or update it (e.g. a
+    /// rate limiter consulting `State` for per-account limits), but only the
+    /// base transport is expected to call `process_response` on success.
+    async fn run<T>(
+        &self,
+        req: T,
+        state: Arc<Mutex<State>>,
+    ) -> Result<ResponseOrError<T::Response>>
+    where
+        T: NashProtocol + Clone + Send + Sync + 'static,
+    {
+        self.inner().run(req, state).await
+    }
+
+    /// Layer `self` with another middleware, mirroring
+    /// `ethers::providers::Middleware::wrap_into`.
+    fn wrap_into<F, M>(self, f: F) -> M
+    where
+        Self: Sized,
+        F: FnOnce(Self) -> M,
+        M: NashMiddleware,
+    {
+        f(self)
+    }
+}
+
+/// Executes the network round trip for a serialized GraphQL request. Kept
+/// pluggable so the base [`Transport`] can be driven by the real Nash
+/// websocket/HTTP client or, in tests, by a canned executor.
+#[async_trait]
+pub trait GraphqlExecutor: Send + Sync + Debug {
+    async fn execute(&self, query: serde_json::Value) -> Result<serde_json::Value>;
+}
+
+/// The base of every middleware stack. Serializes a [`NashProtocol`] request
+/// to GraphQL, executes it, parses the response, and on success lets the
+/// request update [`State`] -- exactly what callers did by hand before this
+/// middleware stack existed.
+#[derive(Debug)]
+pub struct Transport<E> {
+    executor: E,
+}
+
+impl<E: GraphqlExecutor> Transport<E> {
+    pub fn new(executor: E) -> Self {
+        Self { executor }
+    }
+}
+
+#[async_trait]
+impl<E: GraphqlExecutor> NashMiddleware for Transport<E> {
+    type Inner = Self;
+
+    fn inner(&self) -> &Self {
+        self
+    }
+
+    async fn run<T>(
+        &self,
+        req: T,
+        state: Arc<Mutex<State>>,
+    ) -> Result<ResponseOrError<T::Response>>
+    where
+        T: NashProtocol + Clone + Send + Sync + 'static,
+    {
+        let query = req.graphql(state.clone()).await?;
+        let raw = self.executor.execute(query).await?;
+        let parsed = req.response_from_json(raw)?;
+        if let ResponseOrError::Response(ref response) = parsed {
+            req.process_response(response, state).await?;
+        }
+        Ok(parsed)
+    }
+}
@@ -0,0 +1,83 @@
+use super::{NashMiddleware, NashProtocol, ResponseOrError, State};
+use crate::errors::Result;
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use futures_timer::Delay;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Caps the number of requests sent per second, sleeping before forwarding a
+/// request if the stack is currently over the limit. Uses a simple fixed
+/// window rather than a token bucket, which is enough to stay under the
+/// Nash server's rate limits without adding a scheduling dependency.
+#[derive(Debug)]
+pub struct RateLimitMiddleware<M> {
+    inner: M,
+    requests_per_second: u32,
+    window: Mutex<Window>,
+}
+
+#[derive(Debug)]
+struct Window {
+    started_at: Instant,
+    count: u32,
+}
+
+impl<M: NashMiddleware> RateLimitMiddleware<M> {
+    pub fn new(inner: M, requests_per_second: u32) -> Self {
+        Self {
+            inner,
+            requests_per_second,
+            window: Mutex::new(Window {
+                started_at: Instant::now(),
+                count: 0,
+            }),
+        }
+    }
+
+    /// Block until the current window has room for another request.
+    async fn wait_for_slot(&self) {
+        loop {
+            let sleep_for = {
+                let mut window = self.window.lock().await;
+                let elapsed = window.started_at.elapsed();
+                if elapsed >= Duration::from_secs(1) {
+                    window.started_at = Instant::now();
+                    window.count = 0;
+                }
+                if window.count < self.requests_per_second {
+                    window.count += 1;
+                    None
+                } else {
+                    Some(Duration::from_secs(1) - elapsed)
+                }
+            };
+            match sleep_for {
+                None => return,
+                Some(remaining) => Delay::new(remaining).await,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<M: NashMiddleware> NashMiddleware for RateLimitMiddleware<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn run<T>(
+        &self,
+        req: T,
+        state: Arc<Mutex<State>>,
+    ) -> Result<ResponseOrError<T::Response>>
+    where
+        T: NashProtocol + Clone + Send + Sync + 'static,
+    {
+        self.wait_for_slot().await;
+        self.inner().run(req, state).await
+    }
+}
@@ -0,0 +1,144 @@
+use super::{NashMiddleware, NashProtocol, ResponseOrError, State};
+use crate::errors::Result;
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use futures_timer::Delay;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Retries a request with exponential backoff when the inner middleware
+/// returns an `Err` for which [`ProtocolError::is_retryable`] is true, up to
+/// `max_retries` retries after the first attempt; any other error is
+/// returned at once. The base delay doubles after every failed attempt.
+///
+/// [`ProtocolError::is_retryable`]: crate::errors::ProtocolError::is_retryable
+#[derive(Debug)]
+pub struct RetryMiddleware<M> {
+    inner: M,
+    max_retries: u32,
+    base_delay: Duration,
+}
+
+impl<M: NashMiddleware> RetryMiddleware<M> {
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            max_retries: 3,
+            base_delay: Duration::from_millis(200),
+        }
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+}
+
+#[async_trait]
+impl<M: NashMiddleware> NashMiddleware for RetryMiddleware<M> {
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn run<T>(
+        &self,
+        req: T,
+        state: Arc<Mutex<State>>,
+    ) -> Result<ResponseOrError<T::Response>>
+    where
+        T: NashProtocol + Clone + Send + Sync + 'static,
+    {
+        let mut attempt = 0;
+        loop {
+            // `req` and `state` are cheap to clone (requests are plain data,
+            // state is an `Arc`), so each attempt gets its own owned copies.
+            match self.inner().run(req.clone(), state.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(err) if err.is_retryable() && attempt < self.max_retries => {
+                    let backoff = self.base_delay * 2u32.pow(attempt);
+                    Delay::new(backoff).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ProtocolError;
+    use crate::protocol::dh_fill_pool::DhFillPoolRequest;
+    use crate::protocol::middleware::{GraphqlExecutor, Transport};
+    use crate::types::Blockchain;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails every call with whatever `error` produces, counting how many
+    /// times it was asked to run so tests can assert on retry counts
+    /// without needing a real response to parse.
+    #[derive(Debug)]
+    struct FailingExecutor {
+        calls: Arc<AtomicU32>,
+        error: fn() -> ProtocolError,
+    }
+
+    #[async_trait]
+    impl GraphqlExecutor for FailingExecutor {
+        async fn execute(&self, _query: serde_json::Value) -> Result<serde_json::Value> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err((self.error)())
+        }
+    }
+
+    fn state() -> Arc<Mutex<State>> {
+        Arc::new(Mutex::new(
+            State::new(Some("../nash-native-client/test_data/keyfile.json")).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn retries_a_retryable_error_up_to_max_retries() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let executor = FailingExecutor {
+            calls: calls.clone(),
+            error: || ProtocolError::Transport("unreachable".to_string()),
+        };
+        let stack = RetryMiddleware::new(Transport::new(executor))
+            .with_max_retries(2)
+            .with_base_delay(Duration::from_millis(0));
+        let req = DhFillPoolRequest::new(Blockchain::Ethereum).unwrap();
+
+        let result = futures::executor::block_on(stack.run(req, state()));
+
+        assert!(result.is_err());
+        // The initial attempt plus 2 retries.
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn does_not_retry_a_non_retryable_error() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let executor = FailingExecutor {
+            calls: calls.clone(),
+            error: || ProtocolError::Crypto("bad signature".to_string()),
+        };
+        let stack = RetryMiddleware::new(Transport::new(executor))
+            .with_max_retries(2)
+            .with_base_delay(Duration::from_millis(0));
+        let req = DhFillPoolRequest::new(Blockchain::Ethereum).unwrap();
+
+        let result = futures::executor::block_on(stack.run(req, state()));
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
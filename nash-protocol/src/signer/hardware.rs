@@ -0,0 +1,121 @@
+use super::{FillPoolPublics, SharedSecrets, Signer};
+use crate::errors::{ProtocolError, Result};
+use crate::types::blockchain::PublicKey;
+use crate::types::Blockchain;
+
+use async_trait::async_trait;
+
+/// Per-chain BIP-32 derivation path used to ask the device for a key,
+/// following the Ledger integration pattern in ethers-rs.
+fn derivation_path(chain: Blockchain) -> &'static str {
+    match chain {
+        Blockchain::Bitcoin => "m/44'/0'/0'/0/0",
+        Blockchain::Ethereum => "m/44'/60'/0'/0/0",
+        Blockchain::NEO => "m/44'/888'/0'/0/0",
+    }
+}
+
+/// A [`Signer`] backed by a USB HID hardware wallet. Key material never
+/// leaves the device: `sign_payload` sends the payload over to be signed
+/// and waits on the user to confirm it on-device. `generate_pool_publics`/
+/// `combine_pool_secrets` are the pair that lets DH secrets work the same
+/// way -- the device generates and retains each secret, later multiplying it
+/// by the server's public point itself, so only public points and the
+/// combined result ever cross the USB boundary.
+///
+/// This mirrors the Ledger transport used by ethers-rs's `LedgerEthereum`
+/// middleware, generalized to the three curves/chains Nash supports.
+///
+/// **Every method below is a placeholder.** None of them can sign, fetch a
+/// key, or run the DH split yet -- each is one `HidTransport::exchange` call
+/// away from working, but that call needs a real USB HID APDU client talking
+/// to a physical device, which this crate doesn't depend on and can't
+/// fabricate here. Treat `HardwareSigner` as the extension point a real
+/// device integration fills in, not as a usable `Signer` today.
+#[derive(Debug)]
+pub struct HardwareSigner {
+    transport: HidTransport,
+}
+
+/// Build the `Transport` error that `public_key`, `generate_pool_publics`
+/// and `combine_pool_secrets` below return, naming the specific call so the
+/// message stays useful without repeating the same paragraph three times
+/// over. `sign_payload` returns `HidTransport::exchange`'s error instead.
+fn not_yet_implemented(method: &'static str) -> ProtocolError {
+    ProtocolError::Transport(format!(
+        "HardwareSigner::{} needs a real USB HID device integration -- see HardwareSigner's docs",
+        method
+    ))
+}
+
+/// USB HID transport to the device. The actual APDU framing and device
+/// discovery live in a dedicated hardware-wallet crate; this struct just
+/// keeps the open connection.
+#[derive(Debug)]
+pub struct HidTransport {
+    device_path: String,
+}
+
+impl HidTransport {
+    pub fn open(device_path: impl Into<String>) -> Result<Self> {
+        Ok(Self {
+            device_path: device_path.into(),
+        })
+    }
+
+    /// Send an APDU command to the device and wait for its response,
+    /// including however long the user takes to confirm on-device.
+    async fn exchange(&self, _command: &[u8]) -> Result<Vec<u8>> {
+        // No USB HID APDU client talks to `self.device_path` -- this is the
+        // one call a real device integration needs to implement.
+        // `HardwareSigner::sign_payload` below calls it; the other
+        // `HardwareSigner` methods return an error without calling it.
+        Err(ProtocolError::Transport(format!(
+            "HidTransport::exchange has no USB HID client wired up to {}",
+            self.device_path
+        )))
+    }
+}
+
+impl HardwareSigner {
+    pub fn new(transport: HidTransport) -> Self {
+        Self { transport }
+    }
+}
+
+#[async_trait]
+impl Signer for HardwareSigner {
+    async fn sign_payload(&self, chain: Blockchain, payload: &[u8]) -> Result<Vec<u8>> {
+        let mut command = Vec::with_capacity(payload.len() + 1);
+        command.extend_from_slice(derivation_path(chain).as_bytes());
+        command.extend_from_slice(payload);
+        self.transport.exchange(&command).await
+    }
+
+    fn public_key(&self, chain: Blockchain) -> Result<PublicKey> {
+        // Requesting the public key is itself a device round trip; callers
+        // needing this synchronously should cache it after an initial
+        // `sign_payload` or a dedicated async fetch, once `exchange` is
+        // wired up to a real transport.
+        let _ = derivation_path(chain);
+        Err(not_yet_implemented("public_key"))
+    }
+
+    fn generate_pool_publics(&self, _chain: Blockchain, _count: usize) -> Result<FillPoolPublics> {
+        // The device would generate and retain each DH secret itself,
+        // returning only the public points; depends on the same transport
+        // as above.
+        Err(not_yet_implemented("generate_pool_publics"))
+    }
+
+    fn combine_pool_secrets(
+        &self,
+        _chain: Blockchain,
+        _server_publics: &FillPoolPublics,
+    ) -> Result<SharedSecrets> {
+        // The device would multiply its retained secret by each server
+        // public itself and return only the result; depends on the same
+        // transport as above.
+        Err(not_yet_implemented("combine_pool_secrets"))
+    }
+}
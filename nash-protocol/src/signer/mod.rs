@@ -0,0 +1,90 @@
+//! Abstraction over where key material and DH secrets come from. Today
+//! `State::new` loads a `keyfile.json` and signing happens with in-process
+//! `Secp256k1Scalar`/`Secp256r1Scalar` secrets. The [`Signer`] trait lets
+//! that be delegated instead -- to a hardware wallet, an HSM, or anything
+//! else that can produce a signature and a public key without handing the
+//! secret itself to this process.
+//!
+//! The existing keyfile-backed signing becomes [`keyfile::KeyfileSigner`],
+//! one implementation among others rather than the only option.
+//!
+//! [`crate::protocol::dh_fill_pool::pool_manager::ensure_filled`] and
+//! [`crate::protocol::dh_fill_pool::pool_manager::spend`] are the current
+//! callers that take a `&dyn Signer`: a signing operation spends R values
+//! through `spend`, which starts a refill through whichever `Signer` backs
+//! it rather than generating secrets in-process. That refill ends in an
+//! error and adds no R values: `DhFillPoolRequest`'s `process_response`
+//! computes the shared secrets through the `Signer` but does not store them.
+//!
+//! DH secret generation is split across two calls rather than one so a
+//! `Signer` never has to hand its secrets to this process:
+//! [`Signer::generate_pool_publics`] retains the secrets behind the `Signer`
+//! (keyed by `chain`) and returns only public points, and
+//! [`Signer::combine_pool_secrets`] asks the `Signer` to multiply its
+//! retained secret by the server's matching public point and return just the
+//! result -- safe to export, since the discrete-log problem means it can't
+//! be used to recover the secret that produced it. A real `HardwareSigner`
+//! does that multiplication on-device and never lets the secret scalar leave
+//! at all. `DhFillPoolRequest::new_with_signer` drives this split directly;
+//! there is no single-call alternative that hands back raw secrets.
+
+pub mod hardware;
+pub mod keyfile;
+
+use crate::errors::Result;
+use crate::types::blockchain::PublicKey;
+use crate::types::Blockchain;
+
+use async_trait::async_trait;
+use nash_mpc::curves::secp256_k1::{Secp256k1Point, Secp256k1Scalar};
+use nash_mpc::curves::secp256_r1::{Secp256r1Point, Secp256r1Scalar};
+
+/// Public half of a batch of DH secrets generated by
+/// [`Signer::generate_pool_publics`]. Always safe to send to the Nash
+/// server, unlike the secrets `combine_pool_secrets` keeps behind the
+/// `Signer`.
+pub enum FillPoolPublics {
+    K1(Vec<Secp256k1Point>),
+    R1(Vec<Secp256r1Point>),
+}
+
+/// The shared secret produced by [`Signer::combine_pool_secrets`] --
+/// `this_signer's_secret * server_public` for each value in a batch. This,
+/// not the secret that produced it, is what a DH fill-pool response should
+/// end up storing as a usable R value.
+pub enum SharedSecrets {
+    K1(Vec<Secp256k1Scalar>),
+    R1(Vec<Secp256r1Scalar>),
+}
+
+/// A source of signatures and public keys for the chains Nash supports,
+/// abstracting over whether the secret material lives in this process or
+/// behind an external boundary such as a hardware wallet.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Sign an already prefix-encoded payload for `chain`, returning the raw
+    /// signature bytes.
+    async fn sign_payload(&self, chain: Blockchain, payload: &[u8]) -> Result<Vec<u8>>;
+
+    /// The public key this signer presents for `chain`.
+    fn public_key(&self, chain: Blockchain) -> Result<PublicKey>;
+
+    /// Generate `count` new DH secrets for `chain`, returning only their
+    /// public points; the secrets stay behind this `Signer`, keyed by
+    /// `chain`, until a matching `combine_pool_secrets` call.
+    /// `DhFillPoolRequest::new_with_signer` is the caller.
+    fn generate_pool_publics(&self, chain: Blockchain, count: usize) -> Result<FillPoolPublics>;
+
+    /// Combine the secrets from the most recent `generate_pool_publics` call
+    /// for `chain` with the server's matching public points, returning the
+    /// resulting shared secrets. A real hardware wallet performs this
+    /// multiplication on-device, so the secret scalar itself never has to
+    /// leave it -- only this derived value does. `DhFillPoolRequest`'s
+    /// `process_response` is the caller, once the server's publics come
+    /// back.
+    fn combine_pool_secrets(
+        &self,
+        chain: Blockchain,
+        server_publics: &FillPoolPublics,
+    ) -> Result<SharedSecrets>;
+}
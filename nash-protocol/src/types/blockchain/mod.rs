@@ -21,6 +21,9 @@ pub enum Prefix {
     FillOrder,
     Deposit,
     Withdrawal,
+    HtlcLock,
+    HtlcRedeem,
+    HtlcRefund,
 }
 
 impl Prefix {
@@ -30,6 +33,9 @@ impl Prefix {
             Self::FillOrder => [0x01],
             Self::Deposit => [0x02],
             Self::Withdrawal => [0x03],
+            Self::HtlcLock => [0x04],
+            Self::HtlcRedeem => [0x05],
+            Self::HtlcRefund => [0x06],
         }
     }
     pub fn from_bytes(bytes: [u8; 1]) -> Result<Self> {
@@ -38,7 +44,10 @@ impl Prefix {
             [0x01] => Ok(Self::FillOrder),
             [0x02] => Ok(Self::Deposit),
             [0x03] => Ok(Self::Withdrawal),
-            _ => Err(ProtocolError("Invalid prefix byte")),
+            [0x04] => Ok(Self::HtlcLock),
+            [0x05] => Ok(Self::HtlcRedeem),
+            [0x06] => Ok(Self::HtlcRefund),
+            _ => Err(ProtocolError::InvalidPrefix("Invalid prefix byte".to_string())),
         }
     }
 }
@@ -58,6 +67,33 @@ impl Address {
             Blockchain::NEO => Ok(Self::NEO(neo::Address::new(hex_str)?)),
         }
     }
+
+    /// Hex-encoded wire form of this address, in the same format
+    /// `Address::new` parses -- the wire convention this crate already
+    /// follows for `PublicKey::to_hex_str`. Request builders must use this
+    /// instead of `{:?}`, which would serialize the derived `Debug` output
+    /// of the per-chain struct rather than its hex string.
+    pub fn to_hex_str(&self) -> String {
+        match self {
+            Self::Bitcoin(address) => address.to_hex(),
+            Self::Ethereum(address) => address.to_hex(),
+            Self::NEO(address) => address.to_hex(),
+        }
+    }
+
+    /// Raw decoded bytes behind [`to_hex_str`](Self::to_hex_str) -- what a
+    /// signed payload should embed, as opposed to the ASCII characters of
+    /// the hex string itself.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let hex_str = self.to_hex_str();
+        let hex_str = hex_str.strip_prefix("0x").unwrap_or(&hex_str);
+        hex::decode(hex_str).map_err(|_| {
+            ProtocolError::Serialization {
+                message: format!("address hex string was not valid hex: {}", hex_str),
+                source: None,
+            }
+        })
+    }
 }
 
 impl TryFrom<Address> for eth::Address {
@@ -66,8 +102,8 @@ impl TryFrom<Address> for eth::Address {
     fn try_from(address: Address) -> Result<Self> {
         match address {
             Address::Ethereum(address) => Ok(address),
-            _ => Err(ProtocolError(
-                "Tried to convert from something that is not an ETH address",
+            _ => Err(ProtocolError::AddressMismatch(
+                "Tried to convert from something that is not an ETH address".to_string(),
             )),
         }
     }
@@ -79,8 +115,8 @@ impl TryFrom<Address> for neo::Address {
     fn try_from(address: Address) -> Result<Self> {
         match address {
             Address::NEO(address) => Ok(address),
-            _ => Err(ProtocolError(
-                "Tried to convert from something that is not an NEO address",
+            _ => Err(ProtocolError::AddressMismatch(
+                "Tried to convert from something that is not an NEO address".to_string(),
             )),
         }
     }
@@ -92,8 +128,8 @@ impl TryFrom<Address> for btc::Address {
     fn try_from(address: Address) -> Result<Self> {
         match address {
             Address::Bitcoin(address) => Ok(address),
-            _ => Err(ProtocolError(
-                "Tried to convert from something that is not an ETH address",
+            _ => Err(ProtocolError::AddressMismatch(
+                "Tried to convert from something that is not a BTC address".to_string(),
             )),
         }
     }
@@ -147,8 +183,8 @@ impl TryFrom<PublicKey> for eth::PublicKey {
     fn try_from(address: PublicKey) -> Result<Self> {
         match address {
             PublicKey::Ethereum(pub_key) => Ok(pub_key),
-            _ => Err(ProtocolError(
-                "Tried to convert from something that is not an ETH public key",
+            _ => Err(ProtocolError::AddressMismatch(
+                "Tried to convert from something that is not an ETH public key".to_string(),
             )),
         }
     }
@@ -160,8 +196,8 @@ impl TryFrom<PublicKey> for neo::PublicKey {
     fn try_from(address: PublicKey) -> Result<Self> {
         match address {
             PublicKey::NEO(pub_key) => Ok(pub_key),
-            _ => Err(ProtocolError(
-                "Tried to convert from something that is not an NEO public key",
+            _ => Err(ProtocolError::AddressMismatch(
+                "Tried to convert from something that is not an NEO public key".to_string(),
             )),
         }
     }
@@ -173,8 +209,8 @@ impl TryFrom<PublicKey> for btc::PublicKey {
     fn try_from(address: PublicKey) -> Result<Self> {
         match address {
             PublicKey::Bitcoin(pub_key) => Ok(pub_key),
-            _ => Err(ProtocolError(
-                "Tried to convert from something that is not an BTC public key",
+            _ => Err(ProtocolError::AddressMismatch(
+                "Tried to convert from something that is not an BTC public key".to_string(),
             )),
         }
     }